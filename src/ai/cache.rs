@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::reviewer::ReviewResult;
+
+/// Entries older than this are treated as a miss and evicted on read.
+const MAX_AGE: Duration = Duration::from_secs(60 * 60 * 24 * 30);
+
+/// Cap on the number of cached reviews kept on disk; oldest entries are
+/// evicted first once this is exceeded.
+const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    result: ReviewResult,
+    cached_at: u64,
+}
+
+/// Compute a stable cache key over the normalized diff, the model, and the
+/// prompt version, so a prompt change invalidates old cache entries instead
+/// of serving a stale verdict.
+pub fn cache_key(diff: &str, model: &str, prompt_version: u32) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(normalize_diff(diff).as_bytes());
+    hasher.update(b"\0");
+    hasher.update(model.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(prompt_version.to_le_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Line-ending differences from re-staging shouldn't defeat the cache.
+fn normalize_diff(diff: &str) -> String {
+    diff.lines().collect::<Vec<_>>().join("\n")
+}
+
+fn cache_dir() -> PathBuf {
+    PathBuf::from(".git").join("no-dpts-cache")
+}
+
+fn entry_path(key: &str) -> PathBuf {
+    cache_dir().join(format!("{}.json", key))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Look up a cached review result. Expired entries are evicted and treated
+/// as a miss.
+pub fn get(key: &str) -> Option<ReviewResult> {
+    let path = entry_path(key);
+    let content = fs::read_to_string(&path).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+
+    if now_secs().saturating_sub(entry.cached_at) > MAX_AGE.as_secs() {
+        let _ = fs::remove_file(&path);
+        return None;
+    }
+
+    Some(entry.result)
+}
+
+/// Write a review result to the cache, then evict the oldest entries if
+/// we're over the bound.
+pub fn put(key: &str, result: &ReviewResult) -> Result<()> {
+    let dir = cache_dir();
+    fs::create_dir_all(&dir).context("Failed to create AI review cache directory")?;
+
+    let entry = CacheEntry {
+        result: result.clone(),
+        cached_at: now_secs(),
+    };
+    let content = serde_json::to_string(&entry).context("Failed to serialize review cache entry")?;
+    fs::write(entry_path(key), content).context("Failed to write review cache entry")?;
+
+    evict_if_needed(&dir)
+}
+
+fn evict_if_needed(dir: &Path) -> Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().map(|ext| ext == "json").unwrap_or(false))
+        .collect();
+
+    if entries.len() <= MAX_ENTRIES {
+        return Ok(());
+    }
+
+    entries.sort_by_key(|e| {
+        e.metadata()
+            .and_then(|m| m.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    });
+
+    for entry in entries.iter().take(entries.len() - MAX_ENTRIES) {
+        let _ = fs::remove_file(entry.path());
+    }
+
+    Ok(())
+}
@@ -2,14 +2,19 @@ use anyhow::{Context, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use governor::{Quota, RateLimiter, clock::DefaultClock, state::{InMemoryState, NotKeyed}};
 use std::num::NonZeroU32;
 
+use super::cache;
 use crate::config::Config;
 
+/// Bumped whenever REVIEW_PROMPT changes meaning, so cached results from an
+/// older prompt don't get served under the new one.
+const PROMPT_VERSION: u32 = 1;
+
 /// AI Review result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewResult {
     pub passed: bool,
     pub feedback: String,
@@ -75,11 +80,7 @@ fn create_rate_limiter(requests_per_minute: u32) -> Arc<RateLimiter<NotKeyed, In
 }
 
 /// Review code diff using Groq API
-pub async fn review_diff(diff: &str, config: &Config) -> Result<ReviewResult> {
-    // Check for API key
-    let api_key = std::env::var("GROQ_API_KEY")
-        .context("GROQ_API_KEY environment variable not set. Please add it to your .env file.")?;
-    
+pub async fn review_diff(diff: &str, config: &Config, no_cache: bool) -> Result<ReviewResult> {
     if diff.trim().is_empty() {
         return Ok(ReviewResult {
             passed: true,
@@ -87,7 +88,7 @@ pub async fn review_diff(diff: &str, config: &Config) -> Result<ReviewResult> {
             raw_response: String::new(),
         });
     }
-    
+
     // Truncate diff if too large (Groq has token limits)
     let max_diff_chars = 15000;
     let truncated_diff = if diff.len() > max_diff_chars {
@@ -99,7 +100,20 @@ pub async fn review_diff(diff: &str, config: &Config) -> Result<ReviewResult> {
     } else {
         diff.to_string()
     };
-    
+
+    // A cache hit needs neither the API key nor the rate limiter - it's
+    // the same diff we've already reviewed under this model and prompt.
+    let key = cache::cache_key(&truncated_diff, &config.ai_model, PROMPT_VERSION);
+    if !no_cache {
+        if let Some(cached) = cache::get(&key) {
+            return Ok(cached);
+        }
+    }
+
+    // Check for API key
+    let api_key = std::env::var("GROQ_API_KEY")
+        .context("GROQ_API_KEY environment variable not set. Please add it to your .env file.")?;
+
     // Create rate limiter
     let rate_limiter = create_rate_limiter(config.get_rate_limit());
     
@@ -123,7 +137,13 @@ pub async fn review_diff(diff: &str, config: &Config) -> Result<ReviewResult> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(60))
         .build()?;
-    
+
+    let was_truncated = diff.len() > max_diff_chars;
+    // Rough chars-per-token heuristic; good enough for cost accounting, not
+    // meant to match the provider's exact tokenizer.
+    let prompt_tokens_est = truncated_diff.len() / 4;
+
+    let started = Instant::now();
     let response = client
         .post("https://api.groq.com/openai/v1/chat/completions")
         .header("Authorization", format!("Bearer {}", api_key))
@@ -132,18 +152,28 @@ pub async fn review_diff(diff: &str, config: &Config) -> Result<ReviewResult> {
         .send()
         .await
         .context("Failed to connect to Groq API")?;
-    
-    if !response.status().is_success() {
-        let status = response.status();
+
+    let status = response.status();
+    let latency = started.elapsed();
+
+    if !status.is_success() {
         let error_text = response.text().await.unwrap_or_default();
+        tracing::warn!(
+            model = %config.ai_model,
+            prompt_tokens_est,
+            truncated = was_truncated,
+            http_status = status.as_u16(),
+            latency_ms = latency.as_millis() as u64,
+            "groq review request failed"
+        );
         anyhow::bail!("Groq API error ({}): {}", status, error_text);
     }
-    
+
     let groq_response: GroqResponse = response
         .json()
         .await
         .context("Failed to parse Groq API response")?;
-    
+
     let content = groq_response
         .choices
         .first()
@@ -164,11 +194,29 @@ pub async fn review_diff(diff: &str, config: &Config) -> Result<ReviewResult> {
         .trim()
         .to_string();
     
-    Ok(ReviewResult {
+    let result = ReviewResult {
         passed: passed && !rejected,
         feedback: if feedback.is_empty() { content.clone() } else { feedback },
         raw_response: content,
-    })
+    };
+
+    tracing::info!(
+        model = %config.ai_model,
+        prompt_tokens_est,
+        truncated = was_truncated,
+        http_status = status.as_u16(),
+        latency_ms = latency.as_millis() as u64,
+        outcome = if result.passed { "PASS" } else { "REJECT" },
+        "groq review request completed"
+    );
+
+    if !no_cache {
+        if let Err(e) = cache::put(&key, &result) {
+            tracing::warn!(error = %e, "failed to write AI review cache");
+        }
+    }
+
+    Ok(result)
 }
 
 /// Print review result in a formatted way
@@ -2,12 +2,15 @@ use anyhow::Result;
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::fs;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
 
 use crate::ai::reviewer;
 use crate::config::Config;
 use crate::git;
-use crate::scanner::{linter, security};
+use crate::logging::{self, LogFormat};
+use crate::scanner::{annotations, linter, security};
 
 /// Check result summary
 struct CheckSummary {
@@ -20,20 +23,27 @@ struct CheckSummary {
     bypassed: bool,
 }
 
-pub async fn run() -> Result<()> {
-    println!();
-    println!("{}", "╔══════════════════════════════════════════════════════════╗".cyan());
-    println!("{}", "║           🛡️  no-dpts-tool Pre-Commit Check              ║".cyan());
-    println!("{}", "╚══════════════════════════════════════════════════════════╝".cyan());
-    println!();
+pub async fn run(no_cache: bool, log_format: LogFormat) -> Result<()> {
+    let interactive = logging::is_interactive(log_format);
+
+    if interactive {
+        println!();
+        println!("{}", "╔══════════════════════════════════════════════════════════╗".cyan());
+        println!("{}", "║           🛡️  no-dpts-tool Pre-Commit Check              ║".cyan());
+        println!("{}", "╚══════════════════════════════════════════════════════════╝".cyan());
+        println!();
+    }
 
     // Check for bypass sentinel
     let bypass_path = git::get_bypass_sentinel_path();
     if bypass_path.exists() {
         fs::remove_file(&bypass_path)?;
-        println!("{}", "⚡ Bypass token detected - skipping all checks".yellow().bold());
-        println!("{}", "   This is a one-time bypass. Future commits will be checked.".dimmed());
-        println!();
+        tracing::info!(bypassed = true, "check run bypassed");
+        if interactive {
+            println!("{}", "⚡ Bypass token detected - skipping all checks".yellow().bold());
+            println!("{}", "   This is a one-time bypass. Future commits will be checked.".dimmed());
+            println!();
+        }
         return Ok(());
     }
 
@@ -41,13 +51,16 @@ pub async fn run() -> Result<()> {
     let config = Config::load().unwrap_or_default();
 
     // Get staged files
-    let spinner = create_spinner("Detecting staged files...");
+    let spinner = create_spinner("Detecting staged files...", interactive);
     let staged_files = git::get_staged_files()?;
     spinner.finish_with_message(format!("{} Found {} staged file(s)", "✓".green(), staged_files.len()));
 
     if staged_files.is_empty() {
-        println!();
-        println!("{}", "No staged files to check.".dimmed());
+        tracing::info!("no staged files to check");
+        if interactive {
+            println!();
+            println!("{}", "No staged files to check.".dimmed());
+        }
         return Ok(());
     }
 
@@ -59,17 +72,21 @@ pub async fn run() -> Result<()> {
         .collect();
 
     let ignored_count = staged_files.len() - files_to_check.len();
-    if ignored_count > 0 {
+    if interactive && ignored_count > 0 {
         println!("  {} {} file(s) ignored per config", "↳".dimmed(), ignored_count);
     }
 
-    println!();
+    if interactive {
+        println!();
+    }
+
+    tracing::info!(file_count = files_to_check.len(), ignored_count, "check run starting");
 
     // Run all checks in parallel
     let (security_result, linting_result, ai_result) = tokio::join!(
-        run_security_check(&files_to_check, &config),
-        run_linting_check(&files_to_check),
-        run_ai_review(&config)
+        run_security_check(&files_to_check, &config, interactive),
+        run_linting_check(&files_to_check, interactive),
+        run_ai_review(&config, no_cache, interactive)
     );
 
     // Collect results
@@ -84,57 +101,132 @@ pub async fn run() -> Result<()> {
     };
 
     // Print detailed results
-    print_summary(&summary);
+    if interactive {
+        print_summary(&summary);
+    }
+
+    let passed = summary.security_passed && summary.linting_passed && summary.ai_passed;
+    tracing::info!(
+        passed,
+        security_passed = summary.security_passed,
+        linting_passed = summary.linting_passed,
+        ai_passed = summary.ai_passed,
+        "check run complete"
+    );
 
     // Exit with appropriate code
-    if !summary.security_passed || !summary.linting_passed || !summary.ai_passed {
-        println!();
-        println!("{}", "╔══════════════════════════════════════════════════════════╗".red());
-        println!("{}", "║              ❌ COMMIT BLOCKED                           ║".red());
-        println!("{}", "╠══════════════════════════════════════════════════════════╣".red());
-        println!("{}", "║  Fix the issues above, or run:                           ║".red());
-        println!("{}", "║  no-dpts-tool bypass  (emergency skip, use sparingly)    ║".red());
-        println!("{}", "╚══════════════════════════════════════════════════════════╝".red());
-        println!();
+    if !passed {
+        if interactive {
+            println!();
+            println!("{}", "╔══════════════════════════════════════════════════════════╗".red());
+            println!("{}", "║              ❌ COMMIT BLOCKED                           ║".red());
+            println!("{}", "╠══════════════════════════════════════════════════════════╣".red());
+            println!("{}", "║  Fix the issues above, or run:                           ║".red());
+            println!("{}", "║  no-dpts-tool bypass  (emergency skip, use sparingly)    ║".red());
+            println!("{}", "╚══════════════════════════════════════════════════════════╝".red());
+            println!();
+        }
         std::process::exit(1);
     }
 
-    println!();
-    println!("{}", "╔══════════════════════════════════════════════════════════╗".green());
-    println!("{}", "║              ✅ ALL CHECKS PASSED                        ║".green());
-    println!("{}", "╚══════════════════════════════════════════════════════════╝".green());
-    println!();
+    if interactive {
+        println!();
+        println!("{}", "╔══════════════════════════════════════════════════════════╗".green());
+        println!("{}", "║              ✅ ALL CHECKS PASSED                        ║".green());
+        println!("{}", "╚══════════════════════════════════════════════════════════╝".green());
+        println!();
+    }
 
     Ok(())
 }
 
-/// Run security scan on staged files
-async fn run_security_check(files: &[String], config: &Config) -> Result<Vec<security::SecurityFinding>> {
-    let spinner = create_spinner("Running security scan...");
-    
-    let mut all_findings = Vec::new();
-    
+/// Run security scan on staged files. Files are pushed onto a bounded work
+/// queue and pulled by a pool of workers sized to the available parallelism,
+/// so one large file being scanned doesn't stall the rest of the commit.
+pub(crate) async fn run_security_check(
+    files: &[String],
+    config: &Config,
+    interactive: bool,
+) -> Result<Vec<security::SecurityFinding>> {
+    let spinner = create_spinner("Running security scan...", interactive);
+
+    let (work_tx, work_rx) = mpsc::channel::<String>(files.len().max(1));
     for file in files {
-        // Read the staged content of the file
-        match git::read_staged_file_content(file) {
-            Ok(content) => {
-                match security::scan_content(file, &content, config) {
-                    Ok(findings) => all_findings.extend(findings),
-                    Err(e) => eprintln!("{} Error scanning {}: {}", "⚠".yellow(), file, e),
-                }
-            }
-            Err(e) => {
-                // File might be deleted or binary
-                if !e.to_string().contains("fatal") {
-                    eprintln!("{} Could not read {}: {}", "⚠".yellow(), file, e);
+        let _ = work_tx.send(file.clone()).await;
+    }
+    drop(work_tx);
+
+    let work_rx = Arc::new(Mutex::new(work_rx));
+    let (result_tx, mut result_rx) = mpsc::channel::<Vec<security::SecurityFinding>>(files.len().max(1));
+
+    let worker_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(files.len().max(1));
+
+    let mut workers = Vec::with_capacity(worker_count);
+    for _ in 0..worker_count {
+        let work_rx = Arc::clone(&work_rx);
+        let result_tx = result_tx.clone();
+        let config = config.clone();
+
+        workers.push(tokio::spawn(async move {
+            loop {
+                let file = {
+                    let mut rx = work_rx.lock().await;
+                    rx.recv().await
+                };
+                let Some(file) = file else { break };
+
+                let findings = match git::read_staged_file_content(&file) {
+                    Ok(content) => match security::scan_content(&file, &content, &config) {
+                        Ok(findings) => reconcile_with_annotations(&file, &content, findings),
+                        Err(e) => {
+                            tracing::warn!(file = %file, error = %e, "error scanning file");
+                            Vec::new()
+                        }
+                    },
+                    Err(e) => {
+                        // File might be deleted or binary
+                        if !e.to_string().contains("fatal") {
+                            tracing::warn!(file = %file, error = %e, "could not read staged file");
+                        }
+                        Vec::new()
+                    }
+                };
+
+                if result_tx.send(findings).await.is_err() {
+                    break;
                 }
             }
-        }
+        }));
     }
-    
+    drop(result_tx);
+
+    let mut all_findings = Vec::new();
+    while let Some(findings) = result_rx.recv().await {
+        all_findings.extend(findings);
+    }
+
+    for worker in workers {
+        let _ = worker.await;
+    }
+
+    // Findings arrive in whatever order workers finish, so sort for stable,
+    // reviewable output regardless of scheduling.
+    all_findings.sort_by(|a, b| (a.file.as_str(), a.line_number).cmp(&(b.file.as_str(), b.line_number)));
+
     let high_severity = all_findings.iter().filter(|f| f.severity == security::Severity::High).count();
     let medium_severity = all_findings.iter().filter(|f| f.severity == security::Severity::Medium).count();
-    
+
+    tracing::info!(
+        stage = "security",
+        findings = all_findings.len(),
+        high = high_severity,
+        medium = medium_severity,
+        "security scan complete"
+    );
+
     if all_findings.is_empty() {
         spinner.finish_with_message(format!("{} Security scan passed", "✓".green()));
     } else {
@@ -146,32 +238,81 @@ async fn run_security_check(files: &[String], config: &Config) -> Result<Vec<sec
             medium_severity
         ));
     }
-    
+
     Ok(all_findings)
 }
 
-/// Run linting on staged files
-async fn run_linting_check(files: &[String]) -> Result<Vec<linter::LinterResult>> {
-    let spinner = create_spinner("Running linters...");
-    
-    let results = linter::run_linters(files).await;
-    
+/// Reconcile a file's findings against its `no-dpts: allow`/`no-dpts: expect`
+/// directives, printing a warning for any suppression that matched nothing
+/// and folding unmet expectations back in as findings so they still block
+/// the commit.
+pub(crate) fn reconcile_with_annotations(
+    file: &str,
+    content: &str,
+    findings: Vec<security::SecurityFinding>,
+) -> Vec<security::SecurityFinding> {
+    let reconciled = annotations::reconcile(file, content, findings);
+
+    for unused in &reconciled.unused_allows {
+        tracing::warn!(
+            file = %unused.file,
+            line = unused.line,
+            rule = %unused.rule,
+            "unused no-dpts: allow directive matched no finding"
+        );
+    }
+
+    let mut findings = reconciled.findings;
+    findings.extend(reconciled.unmet_expectations.iter().map(annotations::unmet_expectation_finding));
+    findings
+}
+
+/// Run linting on staged files, then reconcile each result against the
+/// file's `no-dpts: allow`/`no-dpts: expect` directives the same way
+/// `run_security_check` does, keyed on tool name rather than line number
+/// since a `LinterResult` is a whole-file pass/fail.
+pub(crate) async fn run_linting_check(files: &[String], interactive: bool) -> Result<Vec<linter::LinterResult>> {
+    let spinner = create_spinner("Running linters...", interactive);
+
+    let raw_results = linter::run_linters(files).await;
+
+    let mut results = Vec::with_capacity(raw_results.len());
+    for result in raw_results {
+        let result = match git::read_staged_file_content(&result.file) {
+            Ok(content) => {
+                let (result, unused) = annotations::reconcile_linter(&result.file, &content, result);
+                if let Some(unused) = unused {
+                    tracing::warn!(
+                        file = %unused.file,
+                        tool = %unused.tool,
+                        "unused no-dpts: allow directive matched no lint failure"
+                    );
+                }
+                result
+            }
+            Err(_) => result,
+        };
+        results.push(result);
+    }
+
     let failed_count = results.iter().filter(|r| !r.passed && !r.skipped).count();
     let checked_count = results.iter().filter(|r| !r.skipped).count();
-    
+
+    tracing::info!(stage = "linting", failed = failed_count, checked = checked_count, "linting complete");
+
     if failed_count == 0 {
         spinner.finish_with_message(format!("{} Linting passed ({} files checked)", "✓".green(), checked_count));
     } else {
         spinner.finish_with_message(format!("{} Linting failed ({}/{} files)", "✗".red(), failed_count, checked_count));
     }
-    
+
     Ok(results)
 }
 
 /// Run AI review on staged diff
-async fn run_ai_review(config: &Config) -> Option<reviewer::ReviewResult> {
-    let spinner = create_spinner("Running AI review...");
-    
+pub(crate) async fn run_ai_review(config: &Config, no_cache: bool, interactive: bool) -> Option<reviewer::ReviewResult> {
+    let spinner = create_spinner("Running AI review...", interactive);
+
     // Get the staged diff
     let diff = match git::get_staged_diff() {
         Ok(d) => d,
@@ -187,7 +328,7 @@ async fn run_ai_review(config: &Config) -> Option<reviewer::ReviewResult> {
     }
     
     // Run the review
-    match reviewer::review_diff(&diff, config).await {
+    match reviewer::review_diff(&diff, config, no_cache).await {
         Ok(result) => {
             if result.passed {
                 spinner.finish_with_message(format!("{} AI review passed", "✓".green()));
@@ -232,8 +373,13 @@ fn print_summary(summary: &CheckSummary) {
     }
 }
 
-/// Create a styled spinner
-fn create_spinner(message: &str) -> ProgressBar {
+/// Create a styled spinner, or a hidden one when output isn't interactive
+/// (piped/CI output shouldn't contain tick-by-tick spinner frames).
+fn create_spinner(message: &str, interactive: bool) -> ProgressBar {
+    if !interactive {
+        return ProgressBar::hidden();
+    }
+
     let spinner = ProgressBar::new_spinner();
     spinner.set_style(
         ProgressStyle::default_spinner()
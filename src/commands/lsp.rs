@@ -0,0 +1,361 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Read, Write};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{sleep, Duration};
+
+use crate::ai::reviewer;
+use crate::config::Config;
+use crate::git;
+use crate::scanner::linter::{self, LinterResult};
+use crate::scanner::security::{self, SecurityFinding};
+
+/// How long to wait after an edit before analyzing a document, so a burst
+/// of keystrokes only triggers one scan.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+static NEXT_PROGRESS_ID: AtomicI64 = AtomicI64::new(1);
+
+/// A document ready to be (re-)analyzed.
+struct AnalysisJob {
+    uri: String,
+    text: String,
+}
+
+/// A finished analysis job, ready to be published as diagnostics.
+struct AnalysisResult {
+    uri: String,
+    findings: Vec<SecurityFinding>,
+    lint_results: Vec<LinterResult>,
+}
+
+enum Action {
+    Analyze(AnalysisJob),
+    ReviewDiff,
+}
+
+/// Run a Language Server over stdio, publishing security findings as
+/// `textDocument/publishDiagnostics` notifications in real time.
+pub async fn run() -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+
+    let (job_tx, job_rx) = mpsc::channel::<AnalysisJob>(64);
+    let (result_tx, mut result_rx) = mpsc::channel::<AnalysisResult>(64);
+    let (rpc_tx, mut rpc_rx) = mpsc::channel::<Value>(64);
+
+    // Reading stdin is blocking I/O, so it gets its own blocking task and
+    // forwards parsed JSON-RPC messages onto the async side.
+    tokio::task::spawn_blocking(move || read_loop(rpc_tx));
+
+    spawn_worker_pool(config.clone(), job_rx, result_tx);
+
+    let mut docs: HashMap<String, String> = HashMap::new();
+    let mut debounced: HashMap<String, tokio::task::JoinHandle<()>> = HashMap::new();
+
+    loop {
+        tokio::select! {
+            message = rpc_rx.recv() => {
+                let Some(message) = message else { break };
+
+                for action in handle_message(&message, &mut docs)? {
+                    match action {
+                        Action::Analyze(job) => {
+                            let uri = job.uri.clone();
+                            if let Some(handle) = debounced.remove(&uri) {
+                                handle.abort();
+                            }
+                            let tx = job_tx.clone();
+                            let handle = tokio::spawn(async move {
+                                sleep(DEBOUNCE).await;
+                                let _ = tx.send(job).await;
+                            });
+                            debounced.insert(uri, handle);
+                        }
+                        Action::ReviewDiff => {
+                            let config = config.clone();
+                            tokio::spawn(async move {
+                                if let Err(e) = run_ai_review_with_progress(&config).await {
+                                    eprintln!("lsp: AI review failed: {}", e);
+                                }
+                            });
+                        }
+                    }
+                }
+            }
+            Some(result) = result_rx.recv() => {
+                publish_diagnostics(&result)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Read one JSON-RPC message from `reader` at a time and forward it,
+/// blocking, until stdin closes or the receiver goes away.
+fn read_loop(tx: mpsc::Sender<Value>) {
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    loop {
+        match read_message(&mut reader) {
+            Ok(Some(value)) => {
+                if tx.blocking_send(value).is_err() {
+                    break;
+                }
+            }
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("lsp: failed to read message: {}", e);
+                break;
+            }
+        }
+    }
+}
+
+/// Parse one `Content-Length`-framed JSON-RPC message.
+fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut content_length: Option<usize> = None;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            content_length = value.trim().parse().ok();
+        }
+    }
+
+    let Some(len) = content_length else {
+        anyhow::bail!("Missing Content-Length header");
+    };
+
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
+    Ok(Some(serde_json::from_slice(&buf)?))
+}
+
+/// Write one `Content-Length`-framed JSON-RPC message to stdout.
+fn write_message(value: &Value) -> Result<()> {
+    let body = serde_json::to_string(value)?;
+    let mut stdout = io::stdout().lock();
+    write!(stdout, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Update our view of a document's content and decide what that message
+/// should trigger. A message can trigger more than one action - `didSave`
+/// both refreshes diagnostics for the saved document and kicks off the AI
+/// review.
+fn handle_message(message: &Value, docs: &mut HashMap<String, String>) -> Result<Vec<Action>> {
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        return Ok(Vec::new());
+    };
+
+    match method {
+        "initialize" => {
+            if let Some(id) = message.get("id") {
+                write_message(&json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "result": {
+                        "capabilities": {
+                            "textDocumentSync": 1
+                        }
+                    }
+                }))?;
+            }
+            Ok(Vec::new())
+        }
+        "textDocument/didOpen" => {
+            let uri = message
+                .pointer("/params/textDocument/uri")
+                .and_then(Value::as_str)
+                .context("didOpen missing textDocument.uri")?
+                .to_string();
+            let text = message
+                .pointer("/params/textDocument/text")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            docs.insert(uri.clone(), text.clone());
+            Ok(vec![Action::Analyze(AnalysisJob { uri, text })])
+        }
+        "textDocument/didChange" => {
+            let uri = message
+                .pointer("/params/textDocument/uri")
+                .and_then(Value::as_str)
+                .context("didChange missing textDocument.uri")?
+                .to_string();
+            let text = message
+                .pointer("/params/contentChanges/0/text")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            docs.insert(uri.clone(), text.clone());
+            Ok(vec![Action::Analyze(AnalysisJob { uri, text })])
+        }
+        "textDocument/didSave" => {
+            let uri = message
+                .pointer("/params/textDocument/uri")
+                .and_then(Value::as_str)
+                .context("didSave missing textDocument.uri")?
+                .to_string();
+
+            let mut actions = Vec::new();
+
+            // Re-analyze the saved document so security/lint diagnostics
+            // reflect what's now on disk, same as didOpen/didChange.
+            if let Some(text) = docs.get(&uri) {
+                actions.push(Action::Analyze(AnalysisJob { uri, text: text.clone() }));
+            }
+
+            // The AI review runs against the staged diff rather than a
+            // single document, so a save is also what kicks it off -
+            // re-running it on every keystroke would be far too expensive.
+            actions.push(Action::ReviewDiff);
+
+            Ok(actions)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Spawn a bounded pool of workers pulling jobs off a shared queue, so one
+/// large file being scanned doesn't delay the rest.
+fn spawn_worker_pool(config: Config, job_rx: mpsc::Receiver<AnalysisJob>, result_tx: mpsc::Sender<AnalysisResult>) {
+    let job_rx = Arc::new(Mutex::new(job_rx));
+    let worker_count = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+
+    for _ in 0..worker_count {
+        let job_rx = Arc::clone(&job_rx);
+        let result_tx = result_tx.clone();
+        let config = config.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let job = {
+                    let mut rx = job_rx.lock().await;
+                    rx.recv().await
+                };
+                let Some(job) = job else { break };
+
+                let path = uri_to_path(&job.uri);
+                let findings = security::scan_content(&path, &job.text, &config).unwrap_or_default();
+                let lint_results = linter::run_linters(&[path]).await;
+                if result_tx.send(AnalysisResult { uri: job.uri, findings, lint_results }).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+fn uri_to_path(uri: &str) -> String {
+    uri.strip_prefix("file://").unwrap_or(uri).to_string()
+}
+
+fn publish_diagnostics(result: &AnalysisResult) -> Result<()> {
+    let mut diagnostics: Vec<Value> = result
+        .findings
+        .iter()
+        .map(|finding| {
+            let line = finding.line_number.saturating_sub(1) as u64;
+            json!({
+                "range": {
+                    "start": { "line": line, "character": 0 },
+                    "end": { "line": line, "character": 200 }
+                },
+                "severity": severity_to_lsp(finding.severity),
+                "source": "no-dpts-tool",
+                "message": format!("[{}] {}", finding.pattern_name, finding.matched_text),
+            })
+        })
+        .collect();
+
+    // Lint failures are whole-file, not line-scoped, so they're reported as
+    // a single diagnostic anchored to the top of the document.
+    diagnostics.extend(result.lint_results.iter().filter(|r| !r.passed && !r.skipped).map(|result| {
+        json!({
+            "range": {
+                "start": { "line": 0, "character": 0 },
+                "end": { "line": 0, "character": 200 }
+            },
+            "severity": 2, // Warning
+            "source": "no-dpts-tool",
+            "message": format!("[{}] {}", result.tool, result.output),
+        })
+    }));
+
+    write_message(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {
+            "uri": result.uri,
+            "diagnostics": diagnostics,
+        }
+    }))
+}
+
+fn severity_to_lsp(severity: security::Severity) -> u8 {
+    match severity {
+        security::Severity::High => 1,   // Error
+        security::Severity::Medium => 2, // Warning
+        security::Severity::Low => 3,    // Information
+    }
+}
+
+/// Run the AI review on the staged diff, reporting progress via `$/progress`
+/// work-done tokens so the editor can show a spinner without blocking
+/// interactive edits.
+async fn run_ai_review_with_progress(config: &Config) -> Result<()> {
+    let diff = git::get_staged_diff().unwrap_or_default();
+    if diff.trim().is_empty() {
+        return Ok(());
+    }
+
+    let token = format!("no-dpts-ai-review-{}", NEXT_PROGRESS_ID.fetch_add(1, Ordering::Relaxed));
+
+    write_message(&json!({
+        "jsonrpc": "2.0",
+        "id": NEXT_PROGRESS_ID.fetch_add(1, Ordering::Relaxed),
+        "method": "window/workDoneProgress/create",
+        "params": { "token": token }
+    }))?;
+
+    write_message(&json!({
+        "jsonrpc": "2.0",
+        "method": "$/progress",
+        "params": {
+            "token": token,
+            "value": { "kind": "begin", "title": "no-dpts-tool AI review", "cancellable": false }
+        }
+    }))?;
+
+    let result = reviewer::review_diff(&diff, config, false).await;
+
+    let message = match &result {
+        Ok(r) if r.passed => "Passed".to_string(),
+        Ok(_) => "Rejected".to_string(),
+        Err(e) => format!("Error: {}", e),
+    };
+
+    write_message(&json!({
+        "jsonrpc": "2.0",
+        "method": "$/progress",
+        "params": {
+            "token": token,
+            "value": { "kind": "end", "message": message }
+        }
+    }))?;
+
+    result.map(|_| ())
+}
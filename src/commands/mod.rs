@@ -0,0 +1,5 @@
+pub mod bypass;
+pub mod check;
+pub mod init;
+pub mod lsp;
+pub mod watch;
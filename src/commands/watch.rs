@@ -0,0 +1,179 @@
+use anyhow::Result;
+use colored::Colorize;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+use crate::ai::reviewer;
+use crate::commands::check::{self, run_linting_check};
+use crate::config::Config;
+use crate::git;
+use crate::scanner::{linter, security};
+
+/// How long to wait after an event before running checks, so a burst of
+/// saves (format-on-save, editor swap files, etc.) coalesces into one run.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+pub async fn run() -> Result<()> {
+    println!("{}", "👀 Watching for file changes (Ctrl+C to stop)...".cyan().bold());
+    println!();
+
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(tx)?;
+    watcher.watch(Path::new("."), RecursiveMode::Recursive)?;
+
+    run_cycle().await?;
+
+    loop {
+        let event = match rx.recv() {
+            Ok(Ok(event)) => event,
+            Ok(Err(e)) => {
+                eprintln!("{} Watcher error: {}", "⚠".yellow(), e);
+                continue;
+            }
+            Err(_) => break,
+        };
+
+        if !is_relevant(&event) {
+            continue;
+        }
+
+        while let Ok(Ok(_)) = rx.recv_timeout(DEBOUNCE) {}
+
+        run_cycle().await?;
+    }
+
+    Ok(())
+}
+
+/// A change inside `.git` (index updates, our own bypass sentinel, etc.)
+/// doesn't represent a source edit and shouldn't trigger a re-run.
+fn is_relevant(event: &Event) -> bool {
+    event
+        .paths
+        .iter()
+        .any(|p| !p.components().any(|c| c.as_os_str() == ".git"))
+}
+
+async fn run_cycle() -> Result<()> {
+    clear_screen();
+
+    println!("{}", "╔══════════════════════════════════════════════════════════╗".cyan());
+    println!("{}", "║                👀 no-dpts-tool Watch Mode                 ║".cyan());
+    println!("{}", "╚══════════════════════════════════════════════════════════╝".cyan());
+    println!();
+
+    let config = Config::load().unwrap_or_default();
+
+    // Re-resolve the files to check from disk on every cycle instead of
+    // caching paths, so an editor-driven rename/move doesn't leave us
+    // watching a file that no longer exists under its old name.
+    let tracked_files = git::get_tracked_files()?;
+    let files_to_check: Vec<String> = tracked_files
+        .into_iter()
+        .filter(|f| !config.should_ignore(f))
+        .collect();
+
+    if files_to_check.is_empty() {
+        println!("{}", "No tracked files to check.".dimmed());
+        return Ok(());
+    }
+
+    // Scan working-tree content directly: the filesystem watcher fires on
+    // disk writes, not `git add`, so the git-index-based helpers the `check`
+    // command uses (`git show`/`git diff --cached`) would keep serving the
+    // last staged or committed snapshot instead of what was just saved.
+    // Linting reuses `run_linting_check`: the linters run on the files on
+    // disk, but the `no-dpts` directives applied to their results are read
+    // from the staged copy of each file.
+    let (security_findings, linter_results, ai_result) = tokio::join!(
+        scan_working_tree(&files_to_check, &config),
+        run_linting_check(&files_to_check, true),
+        review_working_tree(&config)
+    );
+
+    let linter_results = linter_results.unwrap_or_default();
+
+    if !security_findings.is_empty() {
+        security::print_findings(&security_findings);
+    }
+
+    let linter_failed = linter_results.iter().any(|r| !r.passed && !r.skipped);
+    if linter_failed {
+        linter::print_results(&linter_results);
+    }
+
+    if let Some(ref ai) = ai_result {
+        if !ai.passed {
+            reviewer::print_result(ai);
+        }
+    }
+
+    let passed = security_findings.is_empty()
+        && !linter_failed
+        && ai_result.as_ref().map(|r| r.passed).unwrap_or(true);
+
+    println!();
+    if passed {
+        println!("{}", "✅ All checks passed".green().bold());
+    } else {
+        println!("{}", "❌ Issues found".red().bold());
+    }
+    println!();
+    println!("{}", "Watching for changes...".dimmed());
+
+    Ok(())
+}
+
+fn clear_screen() {
+    print!("\x1B[2J\x1B[1;1H");
+    let _ = std::io::stdout().flush();
+}
+
+/// Scan each file's current on-disk content, not its last staged snapshot.
+async fn scan_working_tree(files: &[String], config: &Config) -> Vec<security::SecurityFinding> {
+    let mut all_findings = Vec::new();
+
+    for file in files {
+        match std::fs::read_to_string(file) {
+            Ok(content) => match security::scan_content(file, &content, config) {
+                Ok(findings) => all_findings.extend(check::reconcile_with_annotations(file, &content, findings)),
+                Err(e) => eprintln!("{} Error scanning {}: {}", "⚠".yellow(), file, e),
+            },
+            Err(e) => {
+                // File might be deleted, binary, or briefly missing mid-save
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    eprintln!("{} Could not read {}: {}", "⚠".yellow(), file, e);
+                }
+            }
+        }
+    }
+
+    all_findings
+}
+
+/// Review the working-tree diff against HEAD rather than the staged diff,
+/// so edits show up before they're ever `git add`-ed.
+async fn review_working_tree(config: &Config) -> Option<reviewer::ReviewResult> {
+    let diff = match git::get_working_diff() {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("{} AI review skipped: {}", "⚠".yellow(), e);
+            return None;
+        }
+    };
+
+    if diff.trim().is_empty() {
+        return None;
+    }
+
+    match reviewer::review_diff(&diff, config, false).await {
+        Ok(result) => Some(result),
+        Err(e) => {
+            eprintln!("{} AI review error: {}", "⚠".yellow(), e);
+            None
+        }
+    }
+}
@@ -44,6 +44,28 @@ pub fn get_staged_files() -> Result<Vec<String>> {
     Ok(files)
 }
 
+/// Get the list of files tracked by Git in the working tree
+pub fn get_tracked_files() -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .args(["ls-files"])
+        .output()
+        .context("Failed to execute git ls-files")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Git command failed: {}", stderr);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let files: Vec<String> = stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|s| s.to_string())
+        .collect();
+
+    Ok(files)
+}
+
 /// Get the staged diff for AI review
 pub fn get_staged_diff() -> Result<String> {
     let output = Command::new("git")
@@ -59,6 +81,23 @@ pub fn get_staged_diff() -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// Get the working-tree diff against HEAD, covering both staged and
+/// unstaged edits. Unlike `get_staged_diff`, this reflects changes that
+/// haven't been `git add`-ed yet, which is what a file watcher observes.
+pub fn get_working_diff() -> Result<String> {
+    let output = Command::new("git")
+        .args(["diff", "HEAD"])
+        .output()
+        .context("Failed to execute git diff HEAD")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Git command failed: {}", stderr);
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
 /// Read file content for a staged file
 pub fn read_staged_file_content(file_path: &str) -> Result<String> {
     let output = Command::new("git")
@@ -0,0 +1,60 @@
+use std::io::IsTerminal;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use tracing_subscriber::EnvFilter;
+
+/// Output format for the check runner and reviewer's diagnostic logging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Colored, human-readable output with banners and spinners.
+    Pretty,
+    /// One structured JSON record per event, for CI log pipelines.
+    Json,
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogFormat::Pretty => write!(f, "pretty"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Whether human-only decoration (banners, spinners, ANSI color) should be
+/// shown for the given format and the current stdout.
+pub fn is_interactive(format: LogFormat) -> bool {
+    format == LogFormat::Pretty && std::io::stdout().is_terminal()
+}
+
+/// Initialize the global `tracing` subscriber for the requested format.
+/// Verbosity is controlled by the `NO_DPTS_LOG` environment variable
+/// (defaults to `info`). Diagnostic records always go to stderr, never
+/// stdout - the `lsp` subcommand speaks Content-Length-framed JSON-RPC over
+/// stdout exclusively, and an interleaved log line would corrupt that stream.
+pub fn init(format: LogFormat) -> Result<()> {
+    let filter = EnvFilter::try_from_env("NO_DPTS_LOG").unwrap_or_else(|_| EnvFilter::new("info"));
+
+    match format {
+        LogFormat::Json => {
+            tracing_subscriber::fmt()
+                .json()
+                .with_writer(std::io::stderr)
+                .with_env_filter(filter)
+                .with_target(false)
+                .init();
+        }
+        LogFormat::Pretty => {
+            tracing_subscriber::fmt()
+                .with_writer(std::io::stderr)
+                .with_env_filter(filter)
+                .with_target(false)
+                .with_level(false)
+                .without_time()
+                .init();
+        }
+    }
+
+    Ok(())
+}
@@ -6,8 +6,11 @@ mod ai;
 mod commands;
 mod config;
 mod git;
+mod logging;
 mod scanner;
 
+use logging::LogFormat;
+
 #[derive(Parser)]
 #[command(name = "no-dpts-tool")]
 #[command(author = "Your Team")]
@@ -17,6 +20,10 @@ mod scanner;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Diagnostic output format for the check runner and reviewer
+    #[arg(long, value_enum, global = true, default_value_t = LogFormat::Pretty)]
+    log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
@@ -24,9 +31,17 @@ enum Commands {
     /// Initialize no-dpts-tool in the current Git repository
     Init,
     /// Run all checks on staged files (called by pre-commit hook)
-    Check,
+    Check {
+        /// Skip the AI review cache and force a fresh call for every diff
+        #[arg(long)]
+        no_cache: bool,
+    },
     /// Bypass checks for the next commit (creates a one-time skip token)
     Bypass,
+    /// Continuously watch tracked files and re-run checks as they change
+    Watch,
+    /// Run as a Language Server, publishing findings as live diagnostics
+    Lsp,
 }
 
 #[tokio::main]
@@ -34,11 +49,14 @@ async fn main() -> Result<()> {
     dotenv::dotenv().ok();
     
     let cli = Cli::parse();
+    logging::init(cli.log_format)?;
 
     let result = match cli.command {
         Commands::Init => commands::init::run().await,
-        Commands::Check => commands::check::run().await,
+        Commands::Check { no_cache } => commands::check::run(no_cache, cli.log_format).await,
         Commands::Bypass => commands::bypass::run().await,
+        Commands::Watch => commands::watch::run().await,
+        Commands::Lsp => commands::lsp::run().await,
     };
 
     if let Err(e) = result {
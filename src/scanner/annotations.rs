@@ -0,0 +1,189 @@
+use std::collections::{HashMap, HashSet};
+
+use super::linter::LinterResult;
+use super::security::{Severity, SecurityFinding};
+
+const ALLOW_PREFIX: &str = "no-dpts: allow ";
+const EXPECT_PREFIX: &str = "no-dpts: expect ";
+
+/// Directives parsed from `// no-dpts: allow|expect <rule-id>` comments on a
+/// single line.
+#[derive(Debug, Default, Clone)]
+struct LineDirectives {
+    allow: HashSet<String>,
+    expect: HashSet<String>,
+}
+
+/// 1-based line number -> directives found on that line.
+type DirectiveMap = HashMap<usize, LineDirectives>;
+
+/// A finding the source expected via `no-dpts: expect` that never showed up.
+pub struct ExpectationMiss {
+    pub file: String,
+    pub line: usize,
+    pub rule: String,
+}
+
+/// An `no-dpts: allow` directive that didn't suppress anything.
+pub struct UnusedAllow {
+    pub file: String,
+    pub line: usize,
+    pub rule: String,
+}
+
+/// The outcome of reconciling a file's findings against its annotations.
+pub struct Reconciled {
+    pub findings: Vec<SecurityFinding>,
+    pub unmet_expectations: Vec<ExpectationMiss>,
+    pub unused_allows: Vec<UnusedAllow>,
+}
+
+/// Scan a file's lines for `no-dpts` annotation comments.
+fn parse_directives(content: &str) -> DirectiveMap {
+    let mut map = DirectiveMap::new();
+
+    for (line_number, line) in content.lines().enumerate() {
+        let Some(comment) = line.splitn(2, "//").nth(1) else {
+            continue;
+        };
+        let comment = comment.trim();
+
+        if let Some(rule) = comment.strip_prefix(ALLOW_PREFIX) {
+            map.entry(line_number + 1).or_default().allow.insert(rule.trim().to_string());
+        } else if let Some(rule) = comment.strip_prefix(EXPECT_PREFIX) {
+            map.entry(line_number + 1).or_default().expect.insert(rule.trim().to_string());
+        }
+    }
+
+    map
+}
+
+/// An `allow` directive suppresses a finding on its own line (trailing the
+/// flagged code) or on the line right after it (a standalone comment above
+/// the flagged code).
+fn is_allowed(
+    directives: &DirectiveMap,
+    finding_line: usize,
+    rule: &str,
+    used: &mut HashSet<(usize, String)>,
+) -> bool {
+    for candidate_line in [finding_line, finding_line.saturating_sub(1)] {
+        if candidate_line == 0 {
+            continue;
+        }
+        if let Some(d) = directives.get(&candidate_line) {
+            if d.allow.contains(rule) {
+                used.insert((candidate_line, rule.to_string()));
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Reconcile a file's raw `SecurityFinding`s against its `no-dpts` directives:
+/// drop findings an `allow` suppresses, and flag `expect` directives that had
+/// no matching finding.
+pub fn reconcile(file: &str, content: &str, findings: Vec<SecurityFinding>) -> Reconciled {
+    let directives = parse_directives(content);
+
+    let mut used_allow: HashSet<(usize, String)> = HashSet::new();
+    let mut kept = Vec::new();
+
+    for finding in findings {
+        if is_allowed(&directives, finding.line_number, &finding.pattern_name, &mut used_allow) {
+            continue;
+        }
+        kept.push(finding);
+    }
+
+    let mut unmet_expectations = Vec::new();
+    let mut unused_allows = Vec::new();
+
+    for (&line, d) in &directives {
+        for rule in &d.expect {
+            // Mirror `is_allowed`'s placement rules: the directive may trail
+            // the flagged line, or sit as a standalone comment on the line
+            // right above it.
+            let met = kept
+                .iter()
+                .any(|f| (f.line_number == line || f.line_number == line + 1) && &f.pattern_name == rule);
+            if !met {
+                unmet_expectations.push(ExpectationMiss {
+                    file: file.to_string(),
+                    line,
+                    rule: rule.clone(),
+                });
+            }
+        }
+
+        for rule in &d.allow {
+            if !used_allow.contains(&(line, rule.clone())) {
+                unused_allows.push(UnusedAllow {
+                    file: file.to_string(),
+                    line,
+                    rule: rule.clone(),
+                });
+            }
+        }
+    }
+
+    Reconciled {
+        findings: kept,
+        unmet_expectations,
+        unused_allows,
+    }
+}
+
+/// A `no-dpts: allow <tool>` directive that didn't suppress any lint failure.
+pub struct UnusedLintAllow {
+    pub file: String,
+    pub tool: String,
+}
+
+/// Reconcile a linter result against a file's `no-dpts` directives. Unlike
+/// security findings, a `LinterResult` is a whole-file pass/fail with no line
+/// number to match against, so directives are matched by tool name anywhere
+/// in the file: an `allow <tool>` suppresses a failure from that tool, and an
+/// `expect <tool>` requires it to have failed.
+pub fn reconcile_linter(file: &str, content: &str, mut result: LinterResult) -> (LinterResult, Option<UnusedLintAllow>) {
+    let directives = parse_directives(content);
+    let has_allow = directives.values().any(|d| d.allow.contains(&result.tool));
+    let has_expect = directives.values().any(|d| d.expect.contains(&result.tool));
+
+    let was_failing = !result.passed && !result.skipped;
+    let mut unused = None;
+
+    if has_allow {
+        if was_failing {
+            result.passed = true;
+        } else {
+            unused = Some(UnusedLintAllow {
+                file: file.to_string(),
+                tool: result.tool.clone(),
+            });
+        }
+    }
+
+    if has_expect && result.passed {
+        let outcome = if result.skipped { "was skipped" } else { "passed" };
+        result.passed = false;
+        result.skipped = false;
+        result.output = format!("expected `{}` to fail (no-dpts: expect) but it {}", result.tool, outcome);
+    }
+
+    (result, unused)
+}
+
+/// Turn an unmet `expect` directive into a synthetic high-severity finding so
+/// it flows through the same pass/fail and printing path as any other
+/// security finding.
+pub fn unmet_expectation_finding(miss: &ExpectationMiss) -> SecurityFinding {
+    SecurityFinding {
+        file: miss.file.clone(),
+        line_number: miss.line,
+        pattern_name: "Unmet Expectation".to_string(),
+        matched_text: format!("expected `{}` but no finding matched", miss.rule),
+        severity: Severity::High,
+    }
+}
@@ -0,0 +1,3 @@
+pub mod annotations;
+pub mod linter;
+pub mod security;